@@ -1,5 +1,6 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 use mail_builder::MessageBuilder;
+use mail_send::smtp::auth::Credentials;
 use mail_send::SmtpClientBuilder;
 use serde::{Deserialize, Serialize};
 
@@ -8,13 +9,158 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+/// Where to read the SMTP password from. `Command` lets users keep secrets
+/// out of the app's serialized config, e.g. `password_source: { "Command":
+/// "gpg2 -d ~/.passwords/mail.gpg" }`, mirroring melib's `Password::CommandEval`.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum PasswordSource {
+    Literal(String),
+    Command(String),
+}
+
+/// Resolves a `PasswordSource` to the actual secret, running the command
+/// and trimming its trailing newline if it isn't a literal. The command
+/// runs on a blocking-pool thread since it can take a while (e.g. `gpg2 -d`
+/// blocking on a pinentry prompt) and must not stall the async runtime.
+async fn resolve_password(source: &PasswordSource) -> Result<String, String> {
+    match source {
+        PasswordSource::Literal(password) => Ok(password.clone()),
+        PasswordSource::Command(command) => {
+            let command = command.clone();
+            tokio::task::spawn_blocking(move || {
+                let output = std::process::Command::new("sh")
+                    .arg("-c")
+                    .arg(&command)
+                    .output()
+                    .map_err(|e| format!("Failed to run password command: {}", e))?;
+
+                if !output.status.success() {
+                    return Err(format!(
+                        "Password command exited with {}: {}",
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+
+                String::from_utf8(output.stdout)
+                    .map(|s| s.trim_end_matches(['\n', '\r']).to_string())
+                    .map_err(|e| format!("Password command output is not valid UTF-8: {}", e))
+            })
+            .await
+            .map_err(|e| format!("Password command task panicked: {}", e))?
+        }
+    }
+}
+
+/// How the client should authenticate with the SMTP server.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SmtpAuth {
+    /// Plain username/password, sent via PLAIN or LOGIN.
+    Password {
+        username: String,
+        password: PasswordSource,
+    },
+    /// OAuth2 bearer token, sent via SASL XOAUTH2 (Gmail, Outlook, ...).
+    OAuth2 {
+        username: String,
+        access_token: String,
+    },
+}
+
+/// Builds the SASL XOAUTH2 initial-response string, base64-encoded, per
+/// https://developers.google.com/gmail/imap/xoauth2-protocol.
+fn xoauth2_initial_response(username: &str, access_token: &str) -> String {
+    use base64::Engine;
+    let raw = format!("user={}\x01auth=Bearer {}\x01\x01", username, access_token);
+    base64::engine::general_purpose::STANDARD.encode(raw)
+}
+
+async fn smtp_credentials(auth: &SmtpAuth) -> Result<Credentials<String>, String> {
+    Ok(match auth {
+        SmtpAuth::Password { username, password } => {
+            Credentials::new(username.clone(), resolve_password(password).await?)
+        }
+        SmtpAuth::OAuth2 {
+            username,
+            access_token,
+        } => Credentials::XOauth2 {
+            username: username.clone(),
+            secret: xoauth2_initial_response(username, access_token),
+        },
+    })
+}
+
+/// Which SASL mechanism the server is required to advertise before we'll
+/// attempt to authenticate. This is a pre-flight gate, not a wire-level
+/// selector: `smtp_credentials` builds the same `Credentials::Plain` for
+/// both `Plain` and `Login`, and mail-send itself decides whether to frame
+/// that exchange as PLAIN or LOGIN once it has those credentials — there's
+/// no public API to force one over the other. `Plain`/`Login` here only
+/// pick which capability string `mechanism_supported` checks for; `Auto`
+/// picks LOGIN over PLAIN when advertised but doesn't enforce either.
+/// There's no CRAM-MD5 variant because mail-send has no way to produce
+/// CRAM-MD5 credentials from a plain username/password in the first place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMechanism {
+    Auto,
+    Plain,
+    Login,
+    XOauth2,
+}
+
+impl Default for AuthMechanism {
+    fn default() -> Self {
+        AuthMechanism::Auto
+    }
+}
+
+/// Picks the mechanism `mechanism_supported` should require. `auth` always
+/// wins over `preferred` when it's `OAuth2`, since XOAUTH2 is the only
+/// mechanism we can build credentials for in that case; otherwise `Auto`
+/// prefers LOGIN over PLAIN when the server advertises it. See
+/// `AuthMechanism`'s doc comment: this never reaches into the actual SASL
+/// exchange, only gates whether we proceed to authenticate at all.
+fn negotiate_mechanism(preferred: AuthMechanism, auth: &SmtpAuth, advertised: &[String]) -> AuthMechanism {
+    if matches!(auth, SmtpAuth::OAuth2 { .. }) {
+        return AuthMechanism::XOauth2;
+    }
+    if preferred != AuthMechanism::Auto {
+        return preferred;
+    }
+    let advertises = |name: &str| advertised.iter().any(|m| m.eq_ignore_ascii_case(name));
+    if advertises("LOGIN") {
+        AuthMechanism::Login
+    } else {
+        AuthMechanism::Plain
+    }
+}
+
+/// Checks that the server actually advertised the mechanism we're about to
+/// authenticate with, so a forced `mechanism` choice that the server
+/// doesn't support fails fast with a clear error instead of silently
+/// falling back to whatever `smtp_credentials` happens to send.
+fn mechanism_supported(mechanism: AuthMechanism, advertised: &[String]) -> bool {
+    let name = match mechanism {
+        AuthMechanism::Auto => return true,
+        AuthMechanism::Plain => "PLAIN",
+        AuthMechanism::Login => "LOGIN",
+        AuthMechanism::XOauth2 => "XOAUTH2",
+    };
+    advertised.iter().any(|m| m.eq_ignore_ascii_case(name))
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SmtpConfig {
     pub host: String,
     pub port: u16,
     pub secure: bool, // true = implicit TLS (465), false = STARTTLS (587)
-    pub username: String,
-    pub password: String,
+    pub auth: SmtpAuth,
+    /// Required mechanism gate for `test_smtp_connection`; see
+    /// `AuthMechanism`'s doc comment for what it does and doesn't control.
+    #[serde(default)]
+    pub mechanism: AuthMechanism,
     pub from_email: String,
     pub from_name: Option<String>,
 }
@@ -26,15 +172,95 @@ pub struct EmailAttachment {
     pub content_type: String,
 }
 
+/// DKIM signing algorithm; both are supported by mail-auth's `DkimSigner`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DkimAlgorithm {
+    Rsa,
+    Ed25519,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DkimConfig {
+    pub domain: String,
+    pub selector: String,
+    pub algorithm: DkimAlgorithm,
+    /// PKCS#8 PEM private key: either the PEM text itself, base64-encoded,
+    /// or a filesystem path to a PEM file, per `key_is_path`.
+    pub private_key: String,
+    #[serde(default)]
+    pub key_is_path: bool,
+}
+
+/// Loads the configured key and signs `raw_message`, returning the message
+/// with a `DKIM-Signature` header prepended.
+fn sign_with_dkim(raw_message: &[u8], dkim: &DkimConfig) -> Result<Vec<u8>, String> {
+    use mail_auth::common::crypto::{Ed25519Key, RsaKey, Sha256Rsa};
+    use mail_auth::dkim::DkimSigner;
+
+    let pem = if dkim.key_is_path {
+        std::fs::read_to_string(&dkim.private_key)
+            .map_err(|e| format!("Failed to read DKIM private key from {}: {}", dkim.private_key, e))?
+    } else {
+        use base64::Engine;
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(&dkim.private_key)
+            .map_err(|e| format!("Failed to decode DKIM private key: {}", e))?;
+        String::from_utf8(decoded)
+            .map_err(|e| format!("DKIM private key is not valid UTF-8: {}", e))?
+    };
+
+    let signature = match dkim.algorithm {
+        DkimAlgorithm::Rsa => {
+            let key = RsaKey::<Sha256Rsa>::from_pkcs8_pem(&pem)
+                .map_err(|e| format!("Invalid RSA DKIM private key: {}", e))?;
+            DkimSigner::from_key(key)
+                .domain(dkim.domain.clone())
+                .selector(dkim.selector.clone())
+                .headers(["From", "To", "Subject", "Date"])
+                .sign(raw_message)
+                .map_err(|e| format!("Failed to DKIM-sign message: {}", e))?
+        }
+        DkimAlgorithm::Ed25519 => {
+            let key = Ed25519Key::from_pkcs8_pem(&pem)
+                .map_err(|e| format!("Invalid Ed25519 DKIM private key: {}", e))?;
+            DkimSigner::from_key(key)
+                .domain(dkim.domain.clone())
+                .selector(dkim.selector.clone())
+                .headers(["From", "To", "Subject", "Date"])
+                .sign(raw_message)
+                .map_err(|e| format!("Failed to DKIM-sign message: {}", e))?
+        }
+    };
+
+    let mut signed = signature.to_header().into_bytes();
+    signed.extend_from_slice(raw_message);
+    Ok(signed)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Recipient {
+    pub email: String,
+    pub name: Option<String>,
+}
+
+impl Recipient {
+    fn as_tuple(&self) -> (&str, &str) {
+        (self.name.as_deref().unwrap_or(""), self.email.as_str())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SendEmailRequest {
     pub smtp: SmtpConfig,
-    pub to_email: String,
-    pub to_name: Option<String>,
+    pub to: Vec<Recipient>,
+    pub cc: Option<Vec<Recipient>>,
+    pub bcc: Option<Vec<Recipient>>,
     pub subject: String,
     pub body_html: String,
     pub body_text: Option<String>,
     pub attachments: Option<Vec<EmailAttachment>>,
+    pub dkim: Option<DkimConfig>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -54,18 +280,19 @@ async fn send_email(request: SendEmailRequest) -> Result<SendEmailResponse, Stri
         ("", request.smtp.from_email.as_str())
     };
 
-    let to = if let Some(name) = &request.to_name {
-        (name.as_str(), request.to_email.as_str())
-    } else {
-        ("", request.to_email.as_str())
-    };
-
     let mut message_builder = MessageBuilder::new()
         .from(from)
-        .to(vec![to])
+        .to(request.to.iter().map(Recipient::as_tuple).collect::<Vec<_>>())
         .subject(&request.subject)
         .html_body(&request.body_html);
 
+    // Cc addresses go in the visible headers; Bcc addresses (handled below)
+    // only go in the SMTP envelope, never into the written message.
+    if let Some(cc) = &request.cc {
+        message_builder =
+            message_builder.cc(cc.iter().map(Recipient::as_tuple).collect::<Vec<_>>());
+    }
+
     // Add plain text alternative if provided
     if let Some(text) = &request.body_text {
         message_builder = message_builder.text_body(text);
@@ -87,24 +314,47 @@ async fn send_email(request: SendEmailRequest) -> Result<SendEmailResponse, Stri
     }
 
     // Connect and send
+    let credentials = smtp_credentials(&request.smtp.auth).await?;
     let mut smtp_client = if request.smtp.secure {
         // Implicit TLS (port 465)
         SmtpClientBuilder::new(request.smtp.host.as_str(), request.smtp.port)
             .implicit_tls(true)
-            .credentials((request.smtp.username.as_str(), request.smtp.password.as_str()))
+            .credentials(credentials)
             .connect()
             .await
     } else {
         // STARTTLS (port 587)
         SmtpClientBuilder::new(request.smtp.host.as_str(), request.smtp.port)
             .implicit_tls(false)
-            .credentials((request.smtp.username.as_str(), request.smtp.password.as_str()))
+            .credentials(credentials)
             .connect()
             .await
     }.map_err(|e| format!("Failed to connect to SMTP server: {}", e))?;
 
+    let raw_message = message_builder
+        .write_to_vec()
+        .map_err(|e| format!("Failed to build message: {}", e))?;
+    let raw_message = match &request.dkim {
+        Some(dkim) => sign_with_dkim(&raw_message, dkim)?,
+        None => raw_message,
+    };
+
+    // The envelope RCPT TO list includes Bcc addresses even though they
+    // never appear in a header of the written message above.
+    let mut rcpt_to: Vec<&str> = request.to.iter().map(|r| r.email.as_str()).collect();
+    if let Some(cc) = &request.cc {
+        rcpt_to.extend(cc.iter().map(|r| r.email.as_str()));
+    }
+    if let Some(bcc) = &request.bcc {
+        rcpt_to.extend(bcc.iter().map(|r| r.email.as_str()));
+    }
+
     smtp_client
-        .send(message_builder)
+        .send(mail_send::Message::new(
+            request.smtp.from_email.as_str(),
+            rcpt_to,
+            raw_message,
+        ))
         .await
         .map_err(|e| format!("Failed to send email: {}", e))?;
 
@@ -114,30 +364,66 @@ async fn send_email(request: SendEmailRequest) -> Result<SendEmailResponse, Stri
     })
 }
 
+/// EHLO diagnostics reported back to the frontend so it can show a
+/// pre-flight panel before the user tries to send.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SmtpDiagnostics {
+    pub extensions: Vec<String>,
+    pub auth_mechanisms: Vec<String>,
+    /// The mechanism `mechanism_supported` required to be advertised, not
+    /// necessarily the one mail-send frames on the wire.
+    pub selected_mechanism: AuthMechanism,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TestSmtpConnectionResponse {
+    pub success: bool,
+    pub message: String,
+    pub diagnostics: SmtpDiagnostics,
+}
+
 #[tauri::command]
-async fn test_smtp_connection(smtp: SmtpConfig) -> Result<SendEmailResponse, String> {
-    // Just try to connect to verify credentials
-    let smtp_result = if smtp.secure {
-        SmtpClientBuilder::new(smtp.host.as_str(), smtp.port)
-            .implicit_tls(true)
-            .credentials((smtp.username.as_str(), smtp.password.as_str()))
-            .connect()
-            .await
-    } else {
-        SmtpClientBuilder::new(smtp.host.as_str(), smtp.port)
-            .implicit_tls(false)
-            .credentials((smtp.username.as_str(), smtp.password.as_str()))
-            .connect()
-            .await
-    };
+async fn test_smtp_connection(smtp: SmtpConfig) -> Result<TestSmtpConnectionResponse, String> {
+    let builder = SmtpClientBuilder::new(smtp.host.as_str(), smtp.port).implicit_tls(smtp.secure);
 
-    match smtp_result {
-        Ok(_) => Ok(SendEmailResponse {
-            success: true,
-            message: "SMTP connection successful!".to_string(),
-        }),
-        Err(e) => Err(format!("SMTP connection failed: {}", e)),
+    let mut smtp_client = builder
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to connect to SMTP server: {}", e))?;
+
+    // Inspect the EHLO response before picking an auth mechanism, rather
+    // than blindly calling `.credentials()` like before.
+    let ehlo = smtp_client
+        .capabilities(smtp.host.as_str(), !smtp.secure)
+        .await
+        .map_err(|e| format!("Failed to read EHLO capabilities: {}", e))?;
+
+    let extensions: Vec<String> = ehlo.capabilities().map(|c| c.to_string()).collect();
+    let auth_mechanisms: Vec<String> = ehlo.auth_mechanisms().map(|m| m.to_string()).collect();
+    let selected_mechanism = negotiate_mechanism(smtp.mechanism, &smtp.auth, &auth_mechanisms);
+
+    if !mechanism_supported(selected_mechanism, &auth_mechanisms) {
+        return Err(format!(
+            "Server does not advertise the requested {:?} auth mechanism (advertised: {})",
+            selected_mechanism,
+            auth_mechanisms.join(", ")
+        ));
     }
+
+    smtp_client
+        .authenticate(smtp_credentials(&smtp.auth).await?)
+        .await
+        .map_err(|e| format!("SMTP connection failed: {}", e))?;
+
+    Ok(TestSmtpConnectionResponse {
+        success: true,
+        message: "SMTP connection successful!".to_string(),
+        diagnostics: SmtpDiagnostics {
+            extensions,
+            auth_mechanisms,
+            selected_mechanism,
+        },
+    })
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -1,10 +1,19 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+mod watcher;
+
 use mail_builder::MessageBuilder;
+use mail_send::smtp::auth::Credentials;
 use mail_send::SmtpClientBuilder;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+use watcher::Watcher as _;
 use zip::write::SimpleFileOptions;
 use zip::ZipWriter;
 
@@ -13,13 +22,158 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+/// Where to read the SMTP password from. `Command` lets users keep secrets
+/// out of the app's serialized config, e.g. `password_source: { "Command":
+/// "gpg2 -d ~/.passwords/mail.gpg" }`, mirroring melib's `Password::CommandEval`.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum PasswordSource {
+    Literal(String),
+    Command(String),
+}
+
+/// Resolves a `PasswordSource` to the actual secret, running the command
+/// and trimming its trailing newline if it isn't a literal. The command
+/// runs on a blocking-pool thread since it can take a while (e.g. `gpg2 -d`
+/// blocking on a pinentry prompt) and must not stall the async runtime.
+async fn resolve_password(source: &PasswordSource) -> Result<String, String> {
+    match source {
+        PasswordSource::Literal(password) => Ok(password.clone()),
+        PasswordSource::Command(command) => {
+            let command = command.clone();
+            tokio::task::spawn_blocking(move || {
+                let output = std::process::Command::new("sh")
+                    .arg("-c")
+                    .arg(&command)
+                    .output()
+                    .map_err(|e| format!("Failed to run password command: {}", e))?;
+
+                if !output.status.success() {
+                    return Err(format!(
+                        "Password command exited with {}: {}",
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr)
+                    ));
+                }
+
+                String::from_utf8(output.stdout)
+                    .map(|s| s.trim_end_matches(['\n', '\r']).to_string())
+                    .map_err(|e| format!("Password command output is not valid UTF-8: {}", e))
+            })
+            .await
+            .map_err(|e| format!("Password command task panicked: {}", e))?
+        }
+    }
+}
+
+/// How the client should authenticate with the SMTP server.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SmtpAuth {
+    /// Plain username/password, sent via PLAIN or LOGIN.
+    Password {
+        username: String,
+        password: PasswordSource,
+    },
+    /// OAuth2 bearer token, sent via SASL XOAUTH2 (Gmail, Outlook, ...).
+    OAuth2 {
+        username: String,
+        access_token: String,
+    },
+}
+
+/// Builds the SASL XOAUTH2 initial-response string, base64-encoded, per
+/// https://developers.google.com/gmail/imap/xoauth2-protocol.
+fn xoauth2_initial_response(username: &str, access_token: &str) -> String {
+    use base64::Engine;
+    let raw = format!("user={}\x01auth=Bearer {}\x01\x01", username, access_token);
+    base64::engine::general_purpose::STANDARD.encode(raw)
+}
+
+async fn smtp_credentials(auth: &SmtpAuth) -> Result<Credentials<String>, String> {
+    Ok(match auth {
+        SmtpAuth::Password { username, password } => {
+            Credentials::new(username.clone(), resolve_password(password).await?)
+        }
+        SmtpAuth::OAuth2 {
+            username,
+            access_token,
+        } => Credentials::XOauth2 {
+            username: username.clone(),
+            secret: xoauth2_initial_response(username, access_token),
+        },
+    })
+}
+
+/// Which SASL mechanism the server is required to advertise before we'll
+/// attempt to authenticate. This is a pre-flight gate, not a wire-level
+/// selector: `smtp_credentials` builds the same `Credentials::Plain` for
+/// both `Plain` and `Login`, and mail-send itself decides whether to frame
+/// that exchange as PLAIN or LOGIN once it has those credentials — there's
+/// no public API to force one over the other. `Plain`/`Login` here only
+/// pick which capability string `mechanism_supported` checks for; `Auto`
+/// picks LOGIN over PLAIN when advertised but doesn't enforce either.
+/// There's no CRAM-MD5 variant because mail-send has no way to produce
+/// CRAM-MD5 credentials from a plain username/password in the first place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMechanism {
+    Auto,
+    Plain,
+    Login,
+    XOauth2,
+}
+
+impl Default for AuthMechanism {
+    fn default() -> Self {
+        AuthMechanism::Auto
+    }
+}
+
+/// Picks the mechanism `mechanism_supported` should require. `auth` always
+/// wins over `preferred` when it's `OAuth2`, since XOAUTH2 is the only
+/// mechanism we can build credentials for in that case; otherwise `Auto`
+/// prefers LOGIN over PLAIN when the server advertises it. See
+/// `AuthMechanism`'s doc comment: this never reaches into the actual SASL
+/// exchange, only gates whether we proceed to authenticate at all.
+fn negotiate_mechanism(preferred: AuthMechanism, auth: &SmtpAuth, advertised: &[String]) -> AuthMechanism {
+    if matches!(auth, SmtpAuth::OAuth2 { .. }) {
+        return AuthMechanism::XOauth2;
+    }
+    if preferred != AuthMechanism::Auto {
+        return preferred;
+    }
+    let advertises = |name: &str| advertised.iter().any(|m| m.eq_ignore_ascii_case(name));
+    if advertises("LOGIN") {
+        AuthMechanism::Login
+    } else {
+        AuthMechanism::Plain
+    }
+}
+
+/// Checks that the server actually advertised the mechanism we're about to
+/// authenticate with, so a forced `mechanism` choice that the server
+/// doesn't support fails fast with a clear error instead of silently
+/// falling back to whatever `smtp_credentials` happens to send.
+fn mechanism_supported(mechanism: AuthMechanism, advertised: &[String]) -> bool {
+    let name = match mechanism {
+        AuthMechanism::Auto => return true,
+        AuthMechanism::Plain => "PLAIN",
+        AuthMechanism::Login => "LOGIN",
+        AuthMechanism::XOauth2 => "XOAUTH2",
+    };
+    advertised.iter().any(|m| m.eq_ignore_ascii_case(name))
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SmtpConfig {
     pub host: String,
     pub port: u16,
     pub secure: bool, // true = implicit TLS (465), false = STARTTLS (587)
-    pub username: String,
-    pub password: String,
+    pub auth: SmtpAuth,
+    /// Required mechanism gate for `test_smtp_connection`; see
+    /// `AuthMechanism`'s doc comment for what it does and doesn't control.
+    #[serde(default)]
+    pub mechanism: AuthMechanism,
     pub from_email: String,
     pub from_name: Option<String>,
 }
@@ -31,15 +185,95 @@ pub struct EmailAttachment {
     pub content_type: String,
 }
 
+/// DKIM signing algorithm; both are supported by mail-auth's `DkimSigner`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DkimAlgorithm {
+    Rsa,
+    Ed25519,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DkimConfig {
+    pub domain: String,
+    pub selector: String,
+    pub algorithm: DkimAlgorithm,
+    /// PKCS#8 PEM private key: either the PEM text itself, base64-encoded,
+    /// or a filesystem path to a PEM file, per `key_is_path`.
+    pub private_key: String,
+    #[serde(default)]
+    pub key_is_path: bool,
+}
+
+/// Loads the configured key and signs `raw_message`, returning the message
+/// with a `DKIM-Signature` header prepended.
+fn sign_with_dkim(raw_message: &[u8], dkim: &DkimConfig) -> Result<Vec<u8>, String> {
+    use mail_auth::common::crypto::{Ed25519Key, RsaKey, Sha256Rsa};
+    use mail_auth::dkim::DkimSigner;
+
+    let pem = if dkim.key_is_path {
+        std::fs::read_to_string(&dkim.private_key)
+            .map_err(|e| format!("Failed to read DKIM private key from {}: {}", dkim.private_key, e))?
+    } else {
+        use base64::Engine;
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(&dkim.private_key)
+            .map_err(|e| format!("Failed to decode DKIM private key: {}", e))?;
+        String::from_utf8(decoded)
+            .map_err(|e| format!("DKIM private key is not valid UTF-8: {}", e))?
+    };
+
+    let signature = match dkim.algorithm {
+        DkimAlgorithm::Rsa => {
+            let key = RsaKey::<Sha256Rsa>::from_pkcs8_pem(&pem)
+                .map_err(|e| format!("Invalid RSA DKIM private key: {}", e))?;
+            DkimSigner::from_key(key)
+                .domain(dkim.domain.clone())
+                .selector(dkim.selector.clone())
+                .headers(["From", "To", "Subject", "Date"])
+                .sign(raw_message)
+                .map_err(|e| format!("Failed to DKIM-sign message: {}", e))?
+        }
+        DkimAlgorithm::Ed25519 => {
+            let key = Ed25519Key::from_pkcs8_pem(&pem)
+                .map_err(|e| format!("Invalid Ed25519 DKIM private key: {}", e))?;
+            DkimSigner::from_key(key)
+                .domain(dkim.domain.clone())
+                .selector(dkim.selector.clone())
+                .headers(["From", "To", "Subject", "Date"])
+                .sign(raw_message)
+                .map_err(|e| format!("Failed to DKIM-sign message: {}", e))?
+        }
+    };
+
+    let mut signed = signature.to_header().into_bytes();
+    signed.extend_from_slice(raw_message);
+    Ok(signed)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Recipient {
+    pub email: String,
+    pub name: Option<String>,
+}
+
+impl Recipient {
+    fn as_tuple(&self) -> (&str, &str) {
+        (self.name.as_deref().unwrap_or(""), self.email.as_str())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SendEmailRequest {
     pub smtp: SmtpConfig,
-    pub to_email: String,
-    pub to_name: Option<String>,
+    pub to: Vec<Recipient>,
+    pub cc: Option<Vec<Recipient>>,
+    pub bcc: Option<Vec<Recipient>>,
     pub subject: String,
     pub body_html: String,
     pub body_text: Option<String>,
     pub attachments: Option<Vec<EmailAttachment>>,
+    pub dkim: Option<DkimConfig>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -59,18 +293,19 @@ async fn send_email(request: SendEmailRequest) -> Result<SendEmailResponse, Stri
         ("", request.smtp.from_email.as_str())
     };
 
-    let to = if let Some(name) = &request.to_name {
-        (name.as_str(), request.to_email.as_str())
-    } else {
-        ("", request.to_email.as_str())
-    };
-
     let mut message_builder = MessageBuilder::new()
         .from(from)
-        .to(vec![to])
+        .to(request.to.iter().map(Recipient::as_tuple).collect::<Vec<_>>())
         .subject(&request.subject)
         .html_body(&request.body_html);
 
+    // Cc addresses go in the visible headers; Bcc addresses (handled below)
+    // only go in the SMTP envelope, never into the written message.
+    if let Some(cc) = &request.cc {
+        message_builder =
+            message_builder.cc(cc.iter().map(Recipient::as_tuple).collect::<Vec<_>>());
+    }
+
     // Add plain text alternative if provided
     if let Some(text) = &request.body_text {
         message_builder = message_builder.text_body(text);
@@ -92,24 +327,47 @@ async fn send_email(request: SendEmailRequest) -> Result<SendEmailResponse, Stri
     }
 
     // Connect and send
+    let credentials = smtp_credentials(&request.smtp.auth).await?;
     let mut smtp_client = if request.smtp.secure {
         // Implicit TLS (port 465)
         SmtpClientBuilder::new(request.smtp.host.as_str(), request.smtp.port)
             .implicit_tls(true)
-            .credentials((request.smtp.username.as_str(), request.smtp.password.as_str()))
+            .credentials(credentials)
             .connect()
             .await
     } else {
         // STARTTLS (port 587)
         SmtpClientBuilder::new(request.smtp.host.as_str(), request.smtp.port)
             .implicit_tls(false)
-            .credentials((request.smtp.username.as_str(), request.smtp.password.as_str()))
+            .credentials(credentials)
             .connect()
             .await
     }.map_err(|e| format!("Failed to connect to SMTP server: {}", e))?;
 
+    let raw_message = message_builder
+        .write_to_vec()
+        .map_err(|e| format!("Failed to build message: {}", e))?;
+    let raw_message = match &request.dkim {
+        Some(dkim) => sign_with_dkim(&raw_message, dkim)?,
+        None => raw_message,
+    };
+
+    // The envelope RCPT TO list includes Bcc addresses even though they
+    // never appear in a header of the written message above.
+    let mut rcpt_to: Vec<&str> = request.to.iter().map(|r| r.email.as_str()).collect();
+    if let Some(cc) = &request.cc {
+        rcpt_to.extend(cc.iter().map(|r| r.email.as_str()));
+    }
+    if let Some(bcc) = &request.bcc {
+        rcpt_to.extend(bcc.iter().map(|r| r.email.as_str()));
+    }
+
     smtp_client
-        .send(message_builder)
+        .send(mail_send::Message::new(
+            request.smtp.from_email.as_str(),
+            rcpt_to,
+            raw_message,
+        ))
         .await
         .map_err(|e| format!("Failed to send email: {}", e))?;
 
@@ -119,10 +377,48 @@ async fn send_email(request: SendEmailRequest) -> Result<SendEmailResponse, Stri
     })
 }
 
+const ZIP_STREAM_CHUNK: usize = 64 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionChoice {
+    Stored,
+    Deflated,
+    Bzip2,
+    Zstd,
+}
+
+impl Default for CompressionChoice {
+    fn default() -> Self {
+        CompressionChoice::Deflated
+    }
+}
+
+impl CompressionChoice {
+    fn method(&self) -> zip::CompressionMethod {
+        match self {
+            CompressionChoice::Stored => zip::CompressionMethod::Stored,
+            CompressionChoice::Deflated => zip::CompressionMethod::Deflated,
+            CompressionChoice::Bzip2 => zip::CompressionMethod::Bzip2,
+            CompressionChoice::Zstd => zip::CompressionMethod::Zstd,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateZipRequest {
     pub file_paths: Vec<String>,
     pub output_path: String,
+    /// When set, entries are AES-256 encrypted with this password.
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub compression: CompressionChoice,
+    /// When a `file_paths` entry is a directory, recurse into it and add
+    /// entries under their path relative to that directory. Without this,
+    /// directories are rejected rather than silently flattened.
+    #[serde(default)]
+    pub preserve_paths: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -133,74 +429,225 @@ pub struct CreateZipResponse {
     pub size: u64,
 }
 
+fn zip_options(request: &CreateZipRequest) -> SimpleFileOptions {
+    let options = SimpleFileOptions::default()
+        .compression_method(request.compression.method())
+        .compression_level(matches!(request.compression, CompressionChoice::Deflated).then_some(6));
+
+    match &request.password {
+        Some(password) => options.with_aes_encryption(zip::AesMode::Aes256, password),
+        None => options,
+    }
+}
+
+/// Streams `path` into the zip under `entry_name`, reading in fixed-size
+/// chunks so large files don't have to be buffered in memory.
+fn add_file_to_zip(
+    zip: &mut ZipWriter<File>,
+    path: &Path,
+    entry_name: &str,
+    options: SimpleFileOptions,
+) -> Result<(), String> {
+    zip.start_file(entry_name, options)
+        .map_err(|e| format!("Failed to add file {} to zip: {}", entry_name, e))?;
+
+    let mut file =
+        File::open(path).map_err(|e| format!("Failed to open file {}: {}", path.display(), e))?;
+    let mut buffer = [0u8; ZIP_STREAM_CHUNK];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read file {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        zip.write_all(&buffer[..read])
+            .map_err(|e| format!("Failed to write {} to zip: {}", entry_name, e))?;
+    }
+    Ok(())
+}
+
+/// Recursively adds `path` (a directory) and its contents under `entry_name`.
+fn add_dir_to_zip(
+    zip: &mut ZipWriter<File>,
+    path: &Path,
+    entry_name: &str,
+    options: SimpleFileOptions,
+) -> Result<(), String> {
+    zip.add_directory(format!("{}/", entry_name), options)
+        .map_err(|e| format!("Failed to add directory {} to zip: {}", entry_name, e))?;
+
+    let mut entries: Vec<_> = std::fs::read_dir(path)
+        .map_err(|e| format!("Failed to read directory {}: {}", path.display(), e))?
+        .collect::<std::io::Result<_>>()
+        .map_err(|e| format!("Failed to read directory entry: {}", e))?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let child_path = entry.path();
+        let child_name = format!("{}/{}", entry_name, entry.file_name().to_string_lossy());
+        if child_path.is_dir() {
+            add_dir_to_zip(zip, &child_path, &child_name, options)?;
+        } else {
+            add_file_to_zip(zip, &child_path, &child_name, options)?;
+        }
+    }
+    Ok(())
+}
+
 #[tauri::command]
 fn create_zip(request: CreateZipRequest) -> Result<CreateZipResponse, String> {
     let output_file = File::create(&request.output_path)
         .map_err(|e| format!("Failed to create zip file: {}", e))?;
-    
+
     let mut zip = ZipWriter::new(output_file);
-    let options = SimpleFileOptions::default()
-        .compression_method(zip::CompressionMethod::Deflated)
-        .compression_level(Some(6));
-    
+    let options = zip_options(&request);
+
     for file_path in &request.file_paths {
         let path = Path::new(file_path);
-        let file_name = path.file_name()
+        let entry_name = path
+            .file_name()
             .and_then(|n| n.to_str())
             .ok_or_else(|| format!("Invalid file name: {}", file_path))?;
-        
-        let mut file = File::open(path)
-            .map_err(|e| format!("Failed to open file {}: {}", file_path, e))?;
-        
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)
-            .map_err(|e| format!("Failed to read file {}: {}", file_path, e))?;
-        
-        zip.start_file(file_name, options)
-            .map_err(|e| format!("Failed to add file to zip: {}", e))?;
-        
-        zip.write_all(&buffer)
-            .map_err(|e| format!("Failed to write to zip: {}", e))?;
+
+        if path.is_dir() {
+            if !request.preserve_paths {
+                return Err(format!(
+                    "{} is a directory; set preserve_paths to recurse into it",
+                    file_path
+                ));
+            }
+            add_dir_to_zip(&mut zip, path, entry_name, options)?;
+        } else {
+            add_file_to_zip(&mut zip, path, entry_name, options)?;
+        }
     }
-    
+
     zip.finish()
         .map_err(|e| format!("Failed to finalize zip: {}", e))?;
-    
+
     // Get the file size
     let metadata = std::fs::metadata(&request.output_path)
         .map_err(|e| format!("Failed to get zip metadata: {}", e))?;
-    
+
     Ok(CreateZipResponse {
         success: true,
-        message: format!("Created zip with {} files", request.file_paths.len()),
+        message: format!("Created zip with {} entries", request.file_paths.len()),
         output_path: request.output_path,
         size: metadata.len(),
     })
 }
 
+/// EHLO diagnostics reported back to the frontend so it can show a
+/// pre-flight panel before the user tries to send.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SmtpDiagnostics {
+    pub extensions: Vec<String>,
+    pub auth_mechanisms: Vec<String>,
+    /// The mechanism `mechanism_supported` required to be advertised, not
+    /// necessarily the one mail-send frames on the wire.
+    pub selected_mechanism: AuthMechanism,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TestSmtpConnectionResponse {
+    pub success: bool,
+    pub message: String,
+    pub diagnostics: SmtpDiagnostics,
+}
+
 #[tauri::command]
-async fn test_smtp_connection(smtp: SmtpConfig) -> Result<SendEmailResponse, String> {
-    // Just try to connect to verify credentials
-    let smtp_result = if smtp.secure {
-        SmtpClientBuilder::new(smtp.host.as_str(), smtp.port)
-            .implicit_tls(true)
-            .credentials((smtp.username.as_str(), smtp.password.as_str()))
-            .connect()
-            .await
-    } else {
-        SmtpClientBuilder::new(smtp.host.as_str(), smtp.port)
-            .implicit_tls(false)
-            .credentials((smtp.username.as_str(), smtp.password.as_str()))
-            .connect()
-            .await
-    };
+async fn test_smtp_connection(smtp: SmtpConfig) -> Result<TestSmtpConnectionResponse, String> {
+    let builder = SmtpClientBuilder::new(smtp.host.as_str(), smtp.port).implicit_tls(smtp.secure);
 
-    match smtp_result {
-        Ok(_) => Ok(SendEmailResponse {
-            success: true,
-            message: "SMTP connection successful!".to_string(),
-        }),
-        Err(e) => Err(format!("SMTP connection failed: {}", e)),
+    let mut smtp_client = builder
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to connect to SMTP server: {}", e))?;
+
+    // Inspect the EHLO response before picking an auth mechanism, rather
+    // than blindly calling `.credentials()` like before.
+    let ehlo = smtp_client
+        .capabilities(smtp.host.as_str(), !smtp.secure)
+        .await
+        .map_err(|e| format!("Failed to read EHLO capabilities: {}", e))?;
+
+    let extensions: Vec<String> = ehlo.capabilities().map(|c| c.to_string()).collect();
+    let auth_mechanisms: Vec<String> = ehlo.auth_mechanisms().map(|m| m.to_string()).collect();
+    let selected_mechanism = negotiate_mechanism(smtp.mechanism, &smtp.auth, &auth_mechanisms);
+
+    if !mechanism_supported(selected_mechanism, &auth_mechanisms) {
+        return Err(format!(
+            "Server does not advertise the requested {:?} auth mechanism (advertised: {})",
+            selected_mechanism,
+            auth_mechanisms.join(", ")
+        ));
+    }
+
+    smtp_client
+        .authenticate(smtp_credentials(&smtp.auth).await?)
+        .await
+        .map_err(|e| format!("SMTP connection failed: {}", e))?;
+
+    Ok(TestSmtpConnectionResponse {
+        success: true,
+        message: "SMTP connection successful!".to_string(),
+        diagnostics: SmtpDiagnostics {
+            extensions,
+            auth_mechanisms,
+            selected_mechanism,
+        },
+    })
+}
+
+static NEXT_WATCH_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Active watches, keyed by the id handed back from `start_watch`.
+#[derive(Default)]
+struct WatchRegistry(Mutex<HashMap<u64, Box<dyn watcher::Watcher>>>);
+
+/// Starts a debounced watch on `path` and emits `watch-event` to the
+/// frontend once per path per quiet period instead of once per raw
+/// filesystem notification.
+#[tauri::command]
+fn start_watch(
+    app: AppHandle,
+    registry: State<WatchRegistry>,
+    path: String,
+    recursive: bool,
+    debounce_ms: u64,
+) -> Result<u64, String> {
+    let (raw_tx, raw_rx) = crossbeam_channel::unbounded();
+    let (debounced_tx, debounced_rx) = crossbeam_channel::unbounded();
+
+    let platform_watcher = watcher::PlatformWatcher::new(&path, recursive, raw_tx)?;
+    watcher::debounce::spawn(raw_rx, debounced_tx, Duration::from_millis(debounce_ms));
+
+    let watch_id = NEXT_WATCH_ID.fetch_add(1, AtomicOrdering::Relaxed);
+
+    std::thread::spawn(move || {
+        while let Ok(event) = debounced_rx.recv() {
+            let _ = app.emit(&format!("watch-event-{}", watch_id), event);
+        }
+    });
+
+    registry
+        .0
+        .lock()
+        .unwrap()
+        .insert(watch_id, Box::new(platform_watcher));
+
+    Ok(watch_id)
+}
+
+#[tauri::command]
+fn stop_watch(registry: State<WatchRegistry>, watch_id: u64) -> Result<(), String> {
+    match registry.0.lock().unwrap().remove(&watch_id) {
+        Some(mut watcher) => {
+            watcher.stop();
+            Ok(())
+        }
+        None => Err(format!("No active watch with id {}", watch_id)),
     }
 }
 
@@ -212,7 +659,15 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_os::init())
-        .invoke_handler(tauri::generate_handler![greet, send_email, test_smtp_connection, create_zip])
+        .manage(WatchRegistry::default())
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            send_email,
+            test_smtp_connection,
+            create_zip,
+            start_watch,
+            stop_watch
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,91 @@
+//! Coalesces a burst of raw `WatchEvent`s into one per path per quiet period.
+
+use super::{WatchEvent, WatchEventKind};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use std::collections::HashMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Merges a buffered event with a newly-arrived one for the same path,
+/// collapsing a Remove-then-Create into a single Modify.
+fn merge(previous: WatchEvent, incoming: WatchEvent) -> WatchEvent {
+    let kind = match (previous.kind, &incoming.kind) {
+        (WatchEventKind::Remove, WatchEventKind::Create) => WatchEventKind::Modify,
+        (_, kind) => kind.clone(),
+    };
+    WatchEvent {
+        path: incoming.path,
+        kind,
+    }
+}
+
+/// How long until the least-recently-touched pending path is due to flush,
+/// or `debounce` itself if nothing is pending yet.
+fn next_wait(pending: &HashMap<String, (WatchEvent, Instant)>, debounce: Duration) -> Duration {
+    pending
+        .values()
+        .map(|(_, seen)| debounce.saturating_sub(seen.elapsed()))
+        .min()
+        .unwrap_or(debounce)
+}
+
+/// Flushes every path whose quiet period has elapsed, leaving paths that are
+/// still being actively touched in `pending`. Returns `false` if the
+/// receiving end of `debounced_tx` has gone away, in which case the caller
+/// should stop.
+fn flush_due(
+    pending: &mut HashMap<String, (WatchEvent, Instant)>,
+    debounced_tx: &Sender<WatchEvent>,
+    debounce: Duration,
+) -> bool {
+    let due: Vec<String> = pending
+        .iter()
+        .filter(|(_, (_, seen))| seen.elapsed() >= debounce)
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    for path in due {
+        if let Some((event, _)) = pending.remove(&path) {
+            if debounced_tx.send(event).is_err() {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Spawns a thread that buffers `raw_rx` per path and only forwards an
+/// event on `debounced_tx` once `debounce` has elapsed with no further
+/// changes to that path. Each path is timed independently, so a path that
+/// keeps generating events can never hold up the flush of an unrelated,
+/// already-quiet path. Returns once `raw_rx` disconnects.
+pub fn spawn(raw_rx: Receiver<WatchEvent>, debounced_tx: Sender<WatchEvent>, debounce: Duration) {
+    thread::spawn(move || {
+        let mut pending: HashMap<String, (WatchEvent, Instant)> = HashMap::new();
+        loop {
+            match raw_rx.recv_timeout(next_wait(&pending, debounce)) {
+                Ok(event) => {
+                    let now = Instant::now();
+                    pending
+                        .entry(event.path.clone())
+                        .and_modify(|(existing, seen)| {
+                            *existing = merge(existing.clone(), event.clone());
+                            *seen = now;
+                        })
+                        .or_insert((event, now));
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    if !flush_due(&mut pending, &debounced_tx, debounce) {
+                        return;
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    for (_, (event, _)) in pending.drain() {
+                        let _ = debounced_tx.send(event);
+                    }
+                    return;
+                }
+            }
+        }
+    });
+}
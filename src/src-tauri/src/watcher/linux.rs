@@ -0,0 +1,158 @@
+#![cfg(target_os = "linux")]
+
+use super::{WatchEvent, WatchEventKind, Watcher};
+use crossbeam_channel::Sender;
+use inotify::{EventMask, Inotify, WatchDescriptor, WatchMask};
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const BUFFER_SIZE: usize = 4096;
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+pub struct PlatformWatcher {
+    stop_flag: Arc<AtomicBool>,
+    thread_handle: Option<JoinHandle<()>>,
+}
+
+impl PlatformWatcher {
+    pub fn new<P: AsRef<Path>>(
+        path: P,
+        recursive: bool,
+        event_sender: Sender<WatchEvent>,
+    ) -> Result<Self, String> {
+        let root = path.as_ref().to_path_buf();
+        let mut inotify = Inotify::init().map_err(|e| format!("Failed to init inotify: {}", e))?;
+        let mut watches: HashMap<WatchDescriptor, PathBuf> = HashMap::new();
+        add_watches(&mut inotify, &root, recursive, &mut watches)?;
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let stop_flag_clone = stop_flag.clone();
+
+        let thread_handle = thread::spawn(move || {
+            let mut buffer = [0u8; BUFFER_SIZE];
+            while !stop_flag_clone.load(Ordering::Relaxed) {
+                // `read_events` is non-blocking: it returns `WouldBlock`
+                // immediately instead of waiting for the next event like
+                // `read_events_blocking` does. Polling on an interval lets
+                // us notice `stop_flag` promptly even when the watched tree
+                // is quiet, instead of only after the next filesystem event.
+                let events = match inotify.read_events(&mut buffer) {
+                    Ok(events) => events,
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                        thread::sleep(POLL_INTERVAL);
+                        continue;
+                    }
+                    Err(e) => {
+                        eprintln!("PlatformWatcher: inotify read failed: {}", e);
+                        break;
+                    }
+                };
+
+                for event in events {
+                    let Some(name) = event.name else {
+                        continue;
+                    };
+                    // Reconstruct the path from the directory the watch
+                    // descriptor actually fired on, not always `root` — a
+                    // recursive watch has one inotify watch per
+                    // subdirectory, so `root` is wrong for anything below
+                    // the top level.
+                    let dir = watches.get(&event.wd).cloned().unwrap_or_else(|| root.clone());
+                    let full_path = dir.join(name);
+                    let kind = if event.mask.contains(EventMask::CREATE) {
+                        WatchEventKind::Create
+                    } else if event.mask.contains(EventMask::DELETE) {
+                        WatchEventKind::Remove
+                    } else if event.mask.contains(EventMask::MOVED_FROM)
+                        || event.mask.contains(EventMask::MOVED_TO)
+                    {
+                        WatchEventKind::Rename
+                    } else {
+                        WatchEventKind::Modify
+                    };
+
+                    // A newly-created subdirectory has no inotify watch of
+                    // its own yet — without this, anything created inside
+                    // it would go unseen for the rest of the watch's life.
+                    if recursive && matches!(kind, WatchEventKind::Create) && full_path.is_dir() {
+                        if let Err(e) = add_watches(&mut inotify, &full_path, recursive, &mut watches) {
+                            eprintln!(
+                                "PlatformWatcher: failed to watch new directory {}: {}",
+                                full_path.display(),
+                                e
+                            );
+                        }
+                    }
+
+                    let _ = event_sender.send(WatchEvent {
+                        path: full_path.to_string_lossy().to_string(),
+                        kind,
+                    });
+                }
+            }
+        });
+
+        Ok(PlatformWatcher {
+            stop_flag,
+            thread_handle: Some(thread_handle),
+        })
+    }
+}
+
+fn add_watches(
+    inotify: &mut Inotify,
+    path: &Path,
+    recursive: bool,
+    watches: &mut HashMap<WatchDescriptor, PathBuf>,
+) -> Result<(), String> {
+    let mask = WatchMask::CREATE
+        | WatchMask::DELETE
+        | WatchMask::MODIFY
+        | WatchMask::MOVED_FROM
+        | WatchMask::MOVED_TO
+        | WatchMask::CLOSE_WRITE;
+
+    let wd = inotify
+        .watches()
+        .add(path, mask)
+        .map_err(|e| format!("Failed to watch {}: {}", path.display(), e))?;
+    watches.insert(wd, path.to_path_buf());
+
+    if recursive && path.is_dir() {
+        let entries = std::fs::read_dir(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let entry_path: PathBuf = entry.path();
+            if entry_path.is_dir() {
+                add_watches(inotify, &entry_path, recursive, watches)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl Watcher for PlatformWatcher {
+    fn stop(&mut self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        // The watch thread polls `stop_flag` on an interval rather than
+        // blocking indefinitely, so joining here returns promptly instead
+        // of detaching a thread (and its inotify fd) that would otherwise
+        // only exit on the next unrelated filesystem event.
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for PlatformWatcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
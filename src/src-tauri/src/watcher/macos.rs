@@ -0,0 +1,183 @@
+#![cfg(target_os = "macos")]
+
+use super::{WatchEvent, WatchEventKind, Watcher};
+use crossbeam_channel::Sender;
+use kqueue::{EventFilter, FilterFlag, Ident, Watcher as KqueueWatcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+fn vnode_flags() -> FilterFlag {
+    FilterFlag::NOTE_WRITE | FilterFlag::NOTE_DELETE | FilterFlag::NOTE_RENAME | FilterFlag::NOTE_EXTEND
+}
+
+pub struct PlatformWatcher {
+    stop_flag: Arc<AtomicBool>,
+    thread_handle: Option<JoinHandle<()>>,
+}
+
+impl PlatformWatcher {
+    pub fn new<P: AsRef<Path>>(
+        path: P,
+        recursive: bool,
+        event_sender: Sender<WatchEvent>,
+    ) -> Result<Self, String> {
+        let root = path.as_ref().to_path_buf();
+        let mut watcher =
+            KqueueWatcher::new().map_err(|e| format!("Failed to open kqueue: {}", e))?;
+
+        // kqueue has no recursive-watch flag, so we register every
+        // directory individually and keep a snapshot of each directory's
+        // listing. A `NOTE_WRITE` on a directory's own fd is the only
+        // signal we get that *something* in it changed; diffing against
+        // the snapshot is what turns that into a real Create/Remove on the
+        // child, instead of a bogus Modify on the directory itself.
+        let mut dir_snapshots: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+        watch_tree(&mut watcher, &root, recursive, &mut dir_snapshots)?;
+
+        watcher
+            .watch()
+            .map_err(|e| format!("Failed to start kqueue watch: {}", e))?;
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let stop_flag_clone = stop_flag.clone();
+
+        let thread_handle = thread::spawn(move || {
+            let mut dir_snapshots = dir_snapshots;
+            while !stop_flag_clone.load(Ordering::Relaxed) {
+                let Some(event) = watcher.poll(Some(POLL_INTERVAL)) else {
+                    continue;
+                };
+                let Ident::Filename(_, name) = event.ident else {
+                    continue;
+                };
+                let event_path = PathBuf::from(&name);
+
+                if event.flags.contains(FilterFlag::NOTE_DELETE) {
+                    dir_snapshots.remove(&event_path);
+                    let _ = event_sender.send(WatchEvent {
+                        path: name,
+                        kind: WatchEventKind::Remove,
+                    });
+                    continue;
+                }
+                if event.flags.contains(FilterFlag::NOTE_RENAME) {
+                    let _ = event_sender.send(WatchEvent {
+                        path: name,
+                        kind: WatchEventKind::Rename,
+                    });
+                    continue;
+                }
+
+                if let Some(previous) = dir_snapshots.get(&event_path).cloned() {
+                    // This fd is one of our watched directories: a
+                    // NOTE_WRITE on it means an entry was added or removed.
+                    // Diff the listing to recover which child and which
+                    // kind of change, and, for recursive watches, start
+                    // watching any newly-created subdirectory so further
+                    // changes inside it are picked up too.
+                    let current = list_dir(&event_path);
+                    for added in current.difference(&previous) {
+                        let child_path = event_path.join(added);
+                        let _ = event_sender.send(WatchEvent {
+                            path: child_path.to_string_lossy().to_string(),
+                            kind: WatchEventKind::Create,
+                        });
+                        if recursive && child_path.is_dir() {
+                            let _ = watch_tree(&mut watcher, &child_path, recursive, &mut dir_snapshots);
+                        }
+                    }
+                    for removed in previous.difference(&current) {
+                        let _ = event_sender.send(WatchEvent {
+                            path: event_path.join(removed).to_string_lossy().to_string(),
+                            kind: WatchEventKind::Remove,
+                        });
+                    }
+                    dir_snapshots.insert(event_path, current);
+                } else {
+                    let _ = event_sender.send(WatchEvent {
+                        path: name,
+                        kind: WatchEventKind::Modify,
+                    });
+                }
+            }
+        });
+
+        Ok(PlatformWatcher {
+            stop_flag,
+            thread_handle: Some(thread_handle),
+        })
+    }
+}
+
+/// Registers a kqueue watch on `path` and, if it's a directory, every entry
+/// below it when `recursive` is set, recording a listing snapshot for each
+/// directory along the way.
+///
+/// Known gap: a subdirectory created after the *initial* call (i.e. one
+/// discovered via a `NOTE_WRITE` re-scan rather than this first walk) is
+/// registered on a kqueue that may already be polling — re-adding filenames
+/// after `watch()` has started is best-effort per the `kqueue` crate's API
+/// and isn't guaranteed to pick up every event the new watch could produce
+/// between registration and the next `poll()`.
+fn watch_tree(
+    watcher: &mut KqueueWatcher,
+    path: &Path,
+    recursive: bool,
+    dir_snapshots: &mut HashMap<PathBuf, HashSet<String>>,
+) -> Result<(), String> {
+    watcher
+        .add_filename(path.to_string_lossy().as_ref(), EventFilter::EVFILT_VNODE, vnode_flags())
+        .map_err(|e| format!("Failed to watch {}: {}", path.display(), e))?;
+
+    if path.is_dir() {
+        let children = list_dir(path);
+        dir_snapshots.insert(path.to_path_buf(), children.clone());
+
+        if recursive {
+            for child in &children {
+                let child_path = path.join(child);
+                if child_path.is_dir() {
+                    watch_tree(watcher, &child_path, recursive, dir_snapshots)?;
+                } else {
+                    watcher
+                        .add_filename(child_path.to_string_lossy().as_ref(), EventFilter::EVFILT_VNODE, vnode_flags())
+                        .map_err(|e| format!("Failed to watch {}: {}", child_path.display(), e))?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn list_dir(path: &Path) -> HashSet<String> {
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl Watcher for PlatformWatcher {
+    fn stop(&mut self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.thread_handle.take() {
+            drop(handle);
+        }
+    }
+}
+
+impl Drop for PlatformWatcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
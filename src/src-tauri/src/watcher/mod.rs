@@ -0,0 +1,46 @@
+//! Cross-platform, debounced file watching.
+//!
+//! Each OS gets its own raw backend (`windows`, `macos`, `linux`) behind the
+//! `Watcher` trait; callers never touch those directly. `debounce` sits in
+//! between a backend and the frontend, coalescing the bursts of raw events
+//! editors produce on every save into one event per quiet period.
+
+use serde::Serialize;
+
+pub mod debounce;
+
+#[cfg(windows)]
+mod windows;
+#[cfg(windows)]
+pub use windows::PlatformWatcher;
+
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "macos")]
+pub use macos::PlatformWatcher;
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "linux")]
+pub use linux::PlatformWatcher;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchEventKind {
+    Create,
+    Remove,
+    Modify,
+    Rename,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchEvent {
+    pub path: String,
+    pub kind: WatchEventKind,
+}
+
+/// A running watch on a single directory. Dropping or calling `stop` tears
+/// down the backend's watch thread.
+pub trait Watcher: Send {
+    fn stop(&mut self);
+}
@@ -1,5 +1,6 @@
 #![cfg(windows)]
 
+use super::{WatchEvent, WatchEventKind, Watcher};
 use crossbeam_channel::Sender;
 use std::ffi::OsStr;
 use std::os::windows::ffi::OsStrExt;
@@ -20,29 +21,15 @@ use windows_sys::Win32::Storage::FileSystem::{
 
 const BUFFER_SIZE: usize = 8192;
 
-#[derive(Debug, Clone)]
-pub enum WatchEventKind {
-    Create,
-    Remove,
-    Modify,
-    Rename,
-}
-
-#[derive(Debug, Clone)]
-pub struct WatchEvent {
-    pub path: String,
-    pub kind: WatchEventKind,
-}
-
-pub struct WinWatcher {
+pub struct PlatformWatcher {
     stop_flag: Arc<AtomicBool>,
     thread_handle: Option<JoinHandle<()>>,
 }
 
-unsafe impl Send for WinWatcher {}
-unsafe impl Sync for WinWatcher {}
+unsafe impl Send for PlatformWatcher {}
+unsafe impl Sync for PlatformWatcher {}
 
-impl WinWatcher {
+impl PlatformWatcher {
     pub fn new<P: AsRef<Path>>(
         path: P,
         recursive: bool,
@@ -56,7 +43,7 @@ impl WinWatcher {
             Self::watch_thread(path_str, recursive, event_sender, stop_flag_clone);
         });
 
-        Ok(WinWatcher {
+        Ok(PlatformWatcher {
             stop_flag,
             thread_handle: Some(thread_handle),
         })
@@ -86,7 +73,7 @@ impl WinWatcher {
 
             if dir_handle == INVALID_HANDLE_VALUE {
                 eprintln!(
-                    "WinWatcher: Failed to open directory: error {}",
+                    "PlatformWatcher: Failed to open directory: error {}",
                     GetLastError()
                 );
                 return;
@@ -94,8 +81,8 @@ impl WinWatcher {
 
             let mut buffer: Vec<u8> = vec![0u8; BUFFER_SIZE];
 
-            println!("WinWatcher: Starting watch loop on {}", path);
-            
+            println!("PlatformWatcher: Starting watch loop on {}", path);
+
             // Main watch loop
             while !stop_flag.load(Ordering::Relaxed) {
                 let mut bytes_returned: u32 = 0;
@@ -120,7 +107,7 @@ impl WinWatcher {
                     let err = GetLastError();
                     // Error 995 = operation aborted (expected on shutdown)
                     if err != 995 {
-                        eprintln!("WinWatcher: ReadDirectoryChangesW failed: {}", err);
+                        eprintln!("PlatformWatcher: ReadDirectoryChangesW failed: {}", err);
                     }
                     break;
                 }
@@ -156,9 +143,8 @@ impl WinWatcher {
                         _ => WatchEventKind::Modify,
                     };
 
-                    println!("WinWatcher: Event detected - {} ({:?})", full_path, kind);
                     let _ = event_sender.send(WatchEvent {
-                        path: full_path.clone(),
+                        path: full_path,
                         kind,
                     });
 
@@ -172,8 +158,10 @@ impl WinWatcher {
             CloseHandle(dir_handle);
         }
     }
+}
 
-    pub fn stop(&mut self) {
+impl Watcher for PlatformWatcher {
+    fn stop(&mut self) {
         self.stop_flag.store(true, Ordering::Relaxed);
         if let Some(handle) = self.thread_handle.take() {
             drop(handle);
@@ -181,7 +169,7 @@ impl WinWatcher {
     }
 }
 
-impl Drop for WinWatcher {
+impl Drop for PlatformWatcher {
     fn drop(&mut self) {
         self.stop();
     }